@@ -0,0 +1,380 @@
+//! Serde [`Deserializer`] built on top of the event parser (requires the `serde` feature).
+//!
+//! The document is first collected into an intermediate [`Document`] by a built-in
+//! [`IniHandler`], then walked by [`Deserializer`]: top-level struct fields map to section
+//! names, with options found before the first section available as top-level scalar fields,
+//! and each section maps to a nested struct or a `HashMap<String, String>`.
+
+use std::{collections::HashMap, convert::Infallible, error, fmt, io::Read};
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+
+use crate::{IniHandler, IniParser};
+
+/// Errors that can occur while deserializing an INI document with serde.
+#[derive(Debug)]
+pub enum Error {
+    /// The document could not be parsed as INI.
+    Parse(String),
+    /// Any other deserialization failure (wrong shape, unparsable scalar, ...).
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(msg) => write!(f, "invalid ini: {}", msg),
+            Error::Message(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+/// Sections and global options collected from an INI document.
+#[derive(Debug, Default)]
+struct Document {
+    globals: HashMap<String, String>,
+    sections: HashMap<String, HashMap<String, String>>,
+}
+
+/// Collects a whole document into memory so it can be deserialized afterwards.
+struct CollectHandler {
+    document: Document,
+    current_section: Option<String>,
+}
+
+impl IniHandler for CollectHandler {
+    type Error = Infallible;
+
+    fn section(&mut self, name: &str) -> Result<(), Self::Error> {
+        self.document.sections.entry(name.to_string()).or_default();
+        self.current_section = Some(name.to_string());
+        Ok(())
+    }
+
+    fn option(&mut self, key: &str, value: &str) -> Result<(), Self::Error> {
+        let entry = match &self.current_section {
+            Some(section) => self
+                .document
+                .sections
+                .get_mut(section)
+                .expect("section is inserted by `section` before any option is seen"),
+            None => &mut self.document.globals,
+        };
+        entry.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+fn collect_document<R: Read>(input: R) -> Result<Document, Error> {
+    let mut handler = CollectHandler {
+        document: Document::default(),
+        current_section: None,
+    };
+    let mut parser = IniParser::new(&mut handler);
+    parser
+        .parse(input)
+        .map_err(|err| Error::Parse(err.to_string()))?;
+    Ok(handler.document)
+}
+
+/// What a [`Deserializer`] is currently looking at.
+enum Node<'de> {
+    Root(&'de Document),
+    Section(&'de HashMap<String, String>),
+    Scalar(&'de str),
+}
+
+/// Walks a collected [`Document`] to build a value with serde.
+pub struct Deserializer<'de> {
+    node: Node<'de>,
+}
+
+impl<'de> Deserializer<'de> {
+    fn from_document(document: &'de Document) -> Self {
+        Deserializer {
+            node: Node::Root(document),
+        }
+    }
+}
+
+/// Parses a string into a bool, accepting the usual `true`/`false` plus the common INI
+/// spellings `yes`/`no` and `on`/`off`.
+fn parse_bool(value: &str) -> Result<bool, Error> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => Err(Error::Message(format!("not a boolean: {:?}", value))),
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            match self.node {
+                Node::Scalar(value) => {
+                    let parsed: $ty = value
+                        .parse()
+                        .map_err(|err| Error::Message(format!("{:?}: {}", value, err)))?;
+                    visitor.$visit(parsed)
+                }
+                _ => Err(Error::Message("expected a scalar value".to_string())),
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Root(_) | Node::Section(_) => self.deserialize_map(visitor),
+            Node::Scalar(value) => visitor.visit_borrowed_str(value),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Root(doc) => {
+                let mut keys: Vec<(&str, bool)> = doc
+                    .sections
+                    .keys()
+                    .map(|name| (name.as_str(), true))
+                    .collect();
+                keys.extend(doc.globals.keys().map(|name| (name.as_str(), false)));
+                visitor.visit_map(RootMapAccess {
+                    doc,
+                    keys: keys.into_iter(),
+                    current: None,
+                })
+            }
+            Node::Section(section) => visitor.visit_map(SectionMapAccess {
+                keys: section
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
+            Node::Scalar(_) => Err(Error::Message("expected a section or table".to_string())),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Scalar(value) => visitor.visit_borrowed_str(value),
+            _ => Err(Error::Message("expected a scalar value".to_string())),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Scalar(value) => visitor.visit_bool(parse_bool(value)?),
+            _ => Err(Error::Message("expected a scalar value".to_string())),
+        }
+    }
+
+    deserialize_parsed!(deserialize_i8, visit_i8, i8);
+    deserialize_parsed!(deserialize_i16, visit_i16, i16);
+    deserialize_parsed!(deserialize_i32, visit_i32, i32);
+    deserialize_parsed!(deserialize_i64, visit_i64, i64);
+    deserialize_parsed!(deserialize_u8, visit_u8, u8);
+    deserialize_parsed!(deserialize_u16, visit_u16, u16);
+    deserialize_parsed!(deserialize_u32, visit_u32, u32);
+    deserialize_parsed!(deserialize_u64, visit_u64, u64);
+    deserialize_parsed!(deserialize_f32, visit_f32, f32);
+    deserialize_parsed!(deserialize_f64, visit_f64, f64);
+
+    serde::forward_to_deserialize_any! {
+        char string bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct identifier ignored_any enum
+    }
+}
+
+struct RootMapAccess<'de> {
+    doc: &'de Document,
+    keys: std::vec::IntoIter<(&'de str, bool)>,
+    current: Option<(&'de str, bool)>,
+}
+
+impl<'de> MapAccess<'de> for RootMapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.keys.next() {
+            Some((key, is_section)) => {
+                self.current = Some((key, is_section));
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, is_section) = self
+            .current
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        if is_section {
+            let section = &self.doc.sections[key];
+            seed.deserialize(Deserializer {
+                node: Node::Section(section),
+            })
+        } else {
+            let value = &self.doc.globals[key];
+            seed.deserialize(Deserializer {
+                node: Node::Scalar(value),
+            })
+        }
+    }
+}
+
+struct SectionMapAccess<'de> {
+    keys: std::vec::IntoIter<(&'de str, &'de str)>,
+}
+
+impl<'de> MapAccess<'de> for SectionMapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.keys.as_slice().first() {
+            Some((key, _)) => seed.deserialize((*key).into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (_, value) = self
+            .keys
+            .next()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(Deserializer {
+            node: Node::Scalar(value),
+        })
+    }
+}
+
+/// Deserialize `T` from a string holding a whole INI document.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, Error> {
+    let document = collect_document(s.as_bytes())?;
+    T::deserialize(Deserializer::from_document(&document))
+}
+
+/// Deserialize `T` by reading a whole INI document from `reader`.
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, Error> {
+    let document = collect_document(reader)?;
+    T::deserialize(Deserializer::from_document(&document))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::from_str;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Logging {
+        level: String,
+        verbose: bool,
+        retries: u32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Config {
+        name: String,
+        logging: Logging,
+        extra: HashMap<String, String>,
+    }
+
+    const CONFIG_INI: &str = "name = demo
+
+[logging]
+level = error
+verbose = yes
+retries = 3
+
+[extra]
+foo = bar
+";
+
+    #[test]
+    fn deserialize_struct_with_sections() {
+        let config: Config = from_str(CONFIG_INI).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                name: "demo".to_string(),
+                logging: Logging {
+                    level: "error".to_string(),
+                    verbose: true,
+                    retries: 3,
+                },
+                extra: HashMap::from([("foo".to_string(), "bar".to_string())]),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_missing_section_fails() {
+        #[derive(Deserialize, Debug)]
+        struct WithMissing {
+            #[allow(dead_code)]
+            absent: String,
+        }
+        assert!(from_str::<WithMissing>(CONFIG_INI).is_err());
+    }
+}
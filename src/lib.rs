@@ -40,6 +40,16 @@ use std::{
     path::Path,
 };
 
+#[cfg(feature = "serde")]
+mod de;
+
+#[cfg(feature = "serde")]
+pub use de::{from_reader, from_str, Error as DeError};
+
+mod writer;
+
+pub use writer::IniWriter;
+
 #[derive(Debug)]
 /// Convenient error type for handlers that don't need detailed errors.
 pub struct IniHandlerError {}
@@ -52,10 +62,63 @@ impl fmt::Display for IniHandlerError {
 
 impl error::Error for IniHandlerError {}
 
+/// The specific reason a line was rejected, for actionable diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A `[section` header is missing its closing `]`.
+    MissingSectionClose,
+    /// A line is neither a section, a comment nor a recognizable `key = value` option.
+    MissingDelimiter,
+    /// A quoted value is missing its closing `"`.
+    UnterminatedQuote,
+    /// A quoted value contains a malformed escape sequence (e.g. bad `\xHH` hex digits, or a
+    /// `\xHH` byte above `\x7f`).
+    InvalidEscape,
+    /// A `key = value` line has an empty key.
+    EmptyKey,
+    /// Content was found after a quoted value that isn't whitespace or a comment.
+    TrailingCharacters,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ErrorKind::MissingSectionClose => "missing closing ']'",
+            ErrorKind::MissingDelimiter => "missing key/value delimiter",
+            ErrorKind::UnterminatedQuote => "unterminated quote",
+            ErrorKind::InvalidEscape => "invalid escape sequence",
+            ErrorKind::EmptyKey => "empty key",
+            ErrorKind::TrailingCharacters => "unexpected trailing characters",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+/// A rejected line, with enough context to point the caller at the problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineError {
+    /// 1-based line number.
+    pub lineno: usize,
+    /// 1-based byte column, within the line, where the problem was found.
+    pub column: usize,
+    /// The specific reason the line was rejected.
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.lineno, self.column, self.kind
+        )
+    }
+}
+
 #[derive(Debug)]
 /// Errors for INI format parsing
 pub enum IniError<HandlerError: fmt::Debug + error::Error> {
-    InvalidLine(usize),
+    InvalidLine(LineError),
     Handler(HandlerError),
     Io(io::Error),
 }
@@ -63,7 +126,7 @@ pub enum IniError<HandlerError: fmt::Debug + error::Error> {
 impl<HandlerError: fmt::Debug + error::Error> fmt::Display for IniError<HandlerError> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            IniError::InvalidLine(line) => write!(f, "invalid line: {}", line),
+            IniError::InvalidLine(err) => write!(f, "invalid line: {}", err),
             IniError::Handler(err) => write!(f, "handler error: {:?}", err),
             IniError::Io(err) => write!(f, "input/output error: {:?}", err),
         }
@@ -88,11 +151,18 @@ impl<HandlerError: fmt::Debug + error::Error> From<HandlerError> for IniError<Ha
 
 /// Interface for the INI format handler
 pub trait IniHandler {
-    type Error: fmt::Debug;
+    type Error: fmt::Debug + error::Error;
 
     /// Called when a section is found
     fn section(&mut self, name: &str) -> Result<(), Self::Error>;
 
+    /// Called when a `git-config`-style `[section "subsection"]` header is found. The default
+    /// implementation forwards to [`section`](IniHandler::section) with the names joined as
+    /// `section.subsection`, for handlers that don't care about the distinction.
+    fn subsection(&mut self, section: &str, subsection: &str) -> Result<(), Self::Error> {
+        self.section(&format!("{}.{}", section, subsection))
+    }
+
     /// Called when an option is found
     fn option(&mut self, key: &str, value: &str) -> Result<(), Self::Error>;
 
@@ -100,12 +170,182 @@ pub trait IniHandler {
     fn comment(&mut self, _: &str) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    /// Called by [`IniParser::parse_lenient`] for each line it could not parse or that was
+    /// rejected by another handler method. Return `Ok(())` to skip the line and keep parsing,
+    /// or an `Err` to abort with it.
+    fn error(&mut self, _err: &IniError<Self::Error>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Byte offset of the subslice `sub` within `line`, assuming `sub` was obtained by slicing
+/// `line` (directly or transitively).
+fn byte_offset(line: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - line.as_ptr() as usize
+}
+
+/// Decode the C-style escape sequences used inside quoted values.
+///
+/// `quoted` is the text found between the opening and closing `"`, not including the quotes
+/// themselves. Returns `None` if an escape sequence is malformed. `\xHH` is restricted to the
+/// ASCII range (`\x00`-`\x7f`): bytes above that aren't a character on their own and would need
+/// UTF-8 continuation bytes to mean anything, which this escape doesn't model.
+fn decode_escapes(quoted: &str) -> Option<String> {
+    let mut decoded = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            decoded.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => decoded.push('\\'),
+            '"' => decoded.push('"'),
+            'n' => decoded.push('\n'),
+            't' => decoded.push('\t'),
+            'r' => decoded.push('\r'),
+            '0' => decoded.push('\0'),
+            'x' => {
+                let hi = chars.next()?.to_digit(16)?;
+                let lo = chars.next()?.to_digit(16)?;
+                let byte = (hi << 4) | lo;
+                decoded.push(char::from_u32(byte).filter(|_| byte <= 0x7f)?);
+            }
+            other => decoded.push(other),
+        }
+    }
+    Some(decoded)
+}
+
+/// Why a quoted value could not be read.
+enum QuotedValueError {
+    /// No unescaped closing `"` was found.
+    Unterminated,
+    /// The quote was closed, but its contents hold a malformed escape sequence.
+    InvalidEscape,
+}
+
+/// Read a double-quoted value starting right after the opening `"`.
+///
+/// Returns the decoded value and the rest of the line following the closing quote.
+fn read_quoted_value(rest: &str) -> Result<(String, &str), QuotedValueError> {
+    let mut end = None;
+    let mut escaped = false;
+    for (pos, ch) in rest.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == '"' {
+            end = Some(pos);
+            break;
+        }
+    }
+    let pos = end.ok_or(QuotedValueError::Unterminated)?;
+    let (quoted, after) = rest.split_at(pos);
+    let decoded = decode_escapes(quoted).ok_or(QuotedValueError::InvalidEscape)?;
+    Ok((decoded, &after[1..]))
+}
+
+/// Return true if `line` ends with an odd number of trailing backslashes.
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|&ch| ch == '\\').count() % 2 == 1
+}
+
+/// Decode the escapes `git-config` allows inside a quoted subsection name: `\\` and `\"`.
+/// Returns `None` if any other escape sequence is used.
+fn decode_subsection_escapes(quoted: &str) -> Option<String> {
+    let mut decoded = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            decoded.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => decoded.push('\\'),
+            '"' => decoded.push('"'),
+            _ => return None,
+        }
+    }
+    Some(decoded)
+}
+
+/// Find the `]` that closes a `[...]` header, ignoring any `]` that appears inside a quoted
+/// subsection name (where it's ordinary, unescaped content per git's quoting rules). Returns
+/// `None` if no closing `]` outside of quotes is found.
+fn find_section_close(rest: &str) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (pos, ch) in rest.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes && ch == '\\' {
+            escaped = true;
+        } else if ch == '"' {
+            in_quotes = !in_quotes;
+        } else if ch == ']' && !in_quotes {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// Split a `[section "subsection"]` header's bracket content into its section and decoded
+/// subsection name. Returns `None` for a plain `[section]` header, or one where the trailing
+/// quoted token is malformed.
+fn parse_subsection_header(content: &str) -> Option<(&str, String)> {
+    let content = content.trim();
+    let space = content.find(char::is_whitespace)?;
+    let (name, rest) = content.split_at(space);
+    let rest = rest.trim_start().strip_prefix('"')?;
+    let quoted = rest.strip_suffix('"')?;
+    if quoted.chars().rev().take_while(|&ch| ch == '\\').count() % 2 == 1 {
+        // The final `"` is itself escaped: the quoted value is not actually terminated.
+        return None;
+    }
+    let subsection = decode_subsection_escapes(quoted)?;
+    Some((name, subsection))
+}
+
+/// Find the earliest occurrence of any of `markers` in `text`, returning its position and
+/// length.
+fn find_marker(text: &str, markers: &[String]) -> Option<(usize, usize)> {
+    markers
+        .iter()
+        .filter_map(|marker| text.find(marker.as_str()).map(|pos| (pos, marker.len())))
+        .min_by_key(|&(pos, _)| pos)
+}
+
+/// Find the earliest occurrence of any of `markers` in `text` that starts at a word boundary,
+/// i.e. at the start of `text` or right after whitespace, so a marker character embedded inside
+/// a word (e.g. the `;` in `sec;ret`) isn't mistaken for an inline comment.
+fn find_marker_at_word_boundary(text: &str, markers: &[String]) -> Option<(usize, usize)> {
+    markers
+        .iter()
+        .filter_map(|marker| {
+            text.match_indices(marker.as_str())
+                .find(|&(pos, _)| {
+                    pos == 0
+                        || text[..pos]
+                            .chars()
+                            .next_back()
+                            .is_some_and(char::is_whitespace)
+                })
+                .map(|(pos, _)| (pos, marker.len()))
+        })
+        .min_by_key(|&(pos, _)| pos)
 }
 
 /// INI format parser.
 pub struct IniParser<'a, Error: fmt::Debug + error::Error> {
     handler: &'a mut dyn IniHandler<Error = Error>,
-    start_comment: String,
+    comment_starts: Vec<String>,
+    delimiters: Vec<char>,
+    escape_policy: bool,
+    line_continuation: bool,
+    inline_comments: bool,
 }
 
 impl<'a, Error: fmt::Debug + error::Error> IniParser<'a, Error> {
@@ -119,61 +359,204 @@ impl<'a, Error: fmt::Debug + error::Error> IniParser<'a, Error> {
         handler: &'a mut dyn IniHandler<Error = Error>,
         start_comment: char,
     ) -> IniParser<'a, Error> {
-        let start_comment = format!("{}", start_comment);
         Self {
             handler,
-            start_comment,
+            comment_starts: vec![format!("{}", start_comment)],
+            delimiters: vec!['='],
+            escape_policy: false,
+            line_continuation: false,
+            inline_comments: false,
         }
     }
 
+    /// Accept any of `starts` as a comment start, instead of the single one set at
+    /// construction (e.g. both `;` and `#`, which `rust-ini` treats as equivalent).
+    pub fn with_comment_starts(mut self, starts: &[&str]) -> Self {
+        self.comment_starts = starts.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Accept any of `delimiters` as the key/value separator, instead of only `=` (commonly
+    /// `=` and `:`, as seen across `.ini`/`.properties`-style formats). The first occurrence
+    /// of any of them on a line is used to split the key from the value.
+    pub fn with_delimiters(mut self, delimiters: &[char]) -> Self {
+        self.delimiters = delimiters.to_vec();
+        self
+    }
+
+    /// Enable or disable inline comments.
+    ///
+    /// When enabled, a comment marker found after a value (but outside quotes), at the start of
+    /// the value or right after whitespace, strips the trailing comment from the value and
+    /// reports it through [`IniHandler::comment`]. A marker embedded inside a word (e.g. the
+    /// `;` in `sec;ret`) is left as part of the value. This also applies to the content
+    /// following a quoted value when [`with_escape_policy`](IniParser::with_escape_policy) is
+    /// enabled; without it, such trailing content is rejected as
+    /// [`ErrorKind::TrailingCharacters`].
+    pub fn with_inline_comments(mut self, enable: bool) -> Self {
+        self.inline_comments = enable;
+        self
+    }
+
+    /// Enable or disable quoted values with C-style escape-sequence decoding.
+    ///
+    /// When enabled, a value starting with `"` (after trimming) is read until the next
+    /// unescaped `"` and decoded (`\\`, `\"`, `\n`, `\t`, `\r`, `\0`, `\xHH` and any other
+    /// escaped character, which stands for itself, e.g. `\;`). `\xHH` is restricted to the
+    /// ASCII range (`\x00`-`\x7f`); a higher byte is rejected as
+    /// [`ErrorKind::InvalidEscape`], since it isn't a character on its own and this escape
+    /// doesn't model UTF-8 continuation bytes.
+    pub fn with_escape_policy(mut self, enable: bool) -> Self {
+        self.escape_policy = enable;
+        self
+    }
+
+    /// Enable or disable line continuation.
+    ///
+    /// When enabled, a physical line ending with an odd number of trailing backslashes is
+    /// joined with the next physical line (the final backslash is stripped) before being
+    /// handed to the parser, so a value can span several lines. Bare INI treats a trailing
+    /// backslash literally, hence this is opt-in.
+    pub fn with_line_continuation(mut self, enable: bool) -> Self {
+        self.line_continuation = enable;
+        self
+    }
+
     /// Parse one line without trailing newline character.
     fn parse_ini_line(&mut self, line: &str, lineno: usize) -> Result<(), IniError<Error>> {
-        let line = line.trim_start();
-        if line.is_empty() {
-            Ok(())
-        } else {
-            let (prefix, rest) = if line.is_char_boundary(1) {
-                line.split_at(1)
-            } else {
-                ("", line)
-            };
-            if prefix == "[" {
-                match rest.find(']') {
-                    Some(pos) => {
-                        let (name, _) = rest.split_at(pos);
-                        self.handler.section(name.trim())?;
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            return Ok(());
+        }
+        let err_at = |sub: &str, kind: ErrorKind| {
+            IniError::InvalidLine(LineError {
+                lineno,
+                column: byte_offset(line, sub) + 1,
+                kind,
+            })
+        };
+        if let Some(rest) = trimmed.strip_prefix('[') {
+            match find_section_close(rest) {
+                Some(pos) => {
+                    let (content, _) = rest.split_at(pos);
+                    match parse_subsection_header(content) {
+                        Some((name, subsection)) => {
+                            self.handler.subsection(name.trim(), &subsection)?
+                        }
+                        None => self.handler.section(content.trim())?,
                     }
-                    None => return Err(IniError::InvalidLine(lineno)),
                 }
-            } else if prefix == self.start_comment {
-                self.handler.comment(rest.trim_start())?;
-            } else {
-                match line.find('=') {
-                    Some(pos) => {
-                        let (name, rest) = line.split_at(pos);
-                        let (_, value) = rest.split_at(1);
-                        self.handler.option(name.trim(), value.trim())?;
+                None => return Err(err_at(rest, ErrorKind::MissingSectionClose)),
+            }
+        } else if let Some(marker) = self
+            .comment_starts
+            .iter()
+            .find(|marker| trimmed.starts_with(marker.as_str()))
+        {
+            self.handler.comment(trimmed[marker.len()..].trim_start())?;
+        } else {
+            match trimmed.find(|ch| self.delimiters.contains(&ch)) {
+                Some(pos) => {
+                    let (name, rest) = trimmed.split_at(pos);
+                    let delim_len = rest.chars().next().map_or(1, char::len_utf8);
+                    let (_, value) = rest.split_at(delim_len);
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(err_at(trimmed, ErrorKind::EmptyKey));
+                    }
+                    let value = value.trim();
+                    if self.escape_policy && value.starts_with('"') {
+                        match read_quoted_value(&value[1..]) {
+                            Ok((decoded, after)) => {
+                                let after = after.trim_start();
+                                let leading_comment = self
+                                    .inline_comments
+                                    .then(|| find_marker(after, &self.comment_starts))
+                                    .flatten()
+                                    .filter(|&(pos, _)| pos == 0);
+                                let after = if let Some((_, marker_len)) = leading_comment {
+                                    self.handler.comment(after[marker_len..].trim_start())?;
+                                    ""
+                                } else {
+                                    after
+                                };
+                                if !after.is_empty() {
+                                    return Err(err_at(after, ErrorKind::TrailingCharacters));
+                                }
+                                self.handler.option(name, &decoded)?;
+                            }
+                            Err(QuotedValueError::Unterminated) => {
+                                return Err(err_at(value, ErrorKind::UnterminatedQuote))
+                            }
+                            Err(QuotedValueError::InvalidEscape) => {
+                                return Err(err_at(value, ErrorKind::InvalidEscape))
+                            }
+                        }
+                    } else if self.inline_comments {
+                        match find_marker_at_word_boundary(value, &self.comment_starts) {
+                            Some((pos, marker_len)) => {
+                                let (value, comment) = value.split_at(pos);
+                                self.handler.option(name, value.trim_end())?;
+                                self.handler.comment(comment[marker_len..].trim_start())?;
+                            }
+                            None => self.handler.option(name, value)?,
+                        }
+                    } else {
+                        self.handler.option(name, value)?;
                     }
-                    None => return Err(IniError::InvalidLine(lineno)),
                 }
+                None => return Err(err_at(trimmed, ErrorKind::MissingDelimiter)),
             }
-            Ok(())
         }
+        Ok(())
     }
 
-    /// Parse input from a buffered reader.
-    pub fn parse_buffered<B: BufRead>(&mut self, input: B) -> Result<(), IniError<Error>> {
+    /// Walk `input` line by line, joining continuation lines when enabled, and call `f` for
+    /// each resulting logical line.
+    fn for_each_logical_line<B: BufRead>(
+        &mut self,
+        input: B,
+        mut f: impl FnMut(&mut Self, &str, usize) -> Result<(), IniError<Error>>,
+    ) -> Result<(), IniError<Error>> {
         let mut lineno = 0;
+        let mut buffer = String::new();
+        let mut start_lineno = 0;
+        let mut continuing = false;
         for res in input.lines() {
             lineno += 1;
-            match res {
-                Ok(line) => self.parse_ini_line(line.trim_end(), lineno)?,
-                Err(err) => return Err(IniError::Io(err)),
+            let line = res.map_err(IniError::Io)?;
+            let line = line.trim_end();
+            if self.line_continuation && ends_with_continuation(line) {
+                if !continuing {
+                    start_lineno = lineno;
+                    continuing = true;
+                }
+                buffer.push_str(&line[..line.len() - 1]);
+                continue;
             }
+            if continuing {
+                buffer.push_str(line);
+                f(self, &buffer, start_lineno)?;
+                buffer.clear();
+                continuing = false;
+            } else {
+                f(self, line, lineno)?;
+            }
+        }
+        if continuing {
+            // A trailing backslash on the last line of input has no following line to join
+            // with, so it is treated as a literal backslash.
+            buffer.push('\\');
+            f(self, &buffer, start_lineno)?;
         }
         Ok(())
     }
 
+    /// Parse input from a buffered reader.
+    pub fn parse_buffered<B: BufRead>(&mut self, input: B) -> Result<(), IniError<Error>> {
+        self.for_each_logical_line(input, Self::parse_ini_line)
+    }
+
     /// Parse input from a reader.
     pub fn parse<R: Read>(&mut self, input: R) -> Result<(), IniError<Error>> {
         let mut reader = BufReader::new(input);
@@ -188,12 +571,37 @@ impl<'a, Error: fmt::Debug + error::Error> IniParser<'a, Error> {
         let file = File::open(path).map_err(IniError::Io)?;
         self.parse(file)
     }
+
+    /// Parse input from a reader, recovering from malformed or rejected lines instead of
+    /// aborting on the first one.
+    ///
+    /// Each such line is reported through [`IniHandler::error`]; if it returns `Ok(())` the
+    /// line is skipped and parsing continues, otherwise parsing aborts with that error. All
+    /// skipped errors are collected and returned once parsing is done.
+    pub fn parse_lenient<R: Read>(
+        &mut self,
+        input: R,
+    ) -> Result<Vec<IniError<Error>>, IniError<Error>> {
+        let mut reader = BufReader::new(input);
+        let mut errors = Vec::new();
+        self.for_each_logical_line(&mut reader, |parser, text, lineno| {
+            match parser.parse_ini_line(text, lineno) {
+                Ok(()) => Ok(()),
+                Err(err) => {
+                    parser.handler.error(&err).map_err(IniError::Handler)?;
+                    errors.push(err);
+                    Ok(())
+                }
+            }
+        })?;
+        Ok(errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::{IniError, IniHandler, IniParser};
+    use super::{ErrorKind, IniError, IniHandler, IniParser, LineError};
 
     use std::{
         error, fmt,
@@ -293,6 +701,17 @@ mod tests {
             .map_err(ParserError::Handler)
     }
 
+    fn read_ini_with_escapes(content: &str) -> ParserResult<String> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(content).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_escape_policy(true);
+        parser.parse(buf)?;
+        handler
+            .get()
+            .map(|s| s.to_string())
+            .map_err(ParserError::Handler)
+    }
+
     const VALID_INI: &str = "name = test suite
 
 ; logging section
@@ -339,7 +758,14 @@ level = error
     #[test]
     fn parse_invalid_section() {
         let res = dbg!(read_ini(INVALID_SECTION, None));
-        assert!(matches!(res, Err(IniError::InvalidLine(3))));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 3,
+                kind: ErrorKind::MissingSectionClose,
+                ..
+            }))
+        ));
     }
 
     const INVALID_OPTION: &str = "[logging]
@@ -348,7 +774,14 @@ level error";
     #[test]
     fn parse_invalid_option() {
         let res = dbg!(read_ini(INVALID_OPTION, None));
-        assert!(matches!(res, Err(IniError::InvalidLine(2))));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::MissingDelimiter,
+                ..
+            }))
+        ));
     }
 
     const UNEXPECTED_SECTION: &str = "name = test suite
@@ -367,6 +800,38 @@ level = error
         ));
     }
 
+    const GIT_STYLE_SUBSECTION: &str = "[remote \"origin\"]
+url = git@example.com:repo.git
+";
+
+    #[test]
+    fn parse_git_style_subsection() {
+        let result = read_ini(GIT_STYLE_SUBSECTION, None).unwrap();
+        assert_eq!("<remote.origin>(url=git@example.com:repo.git)", result);
+    }
+
+    const GIT_STYLE_SUBSECTION_WITH_ESCAPES: &str = r#"[branch "feature\"x\\y"]
+merge = refs/heads/main
+"#;
+
+    #[test]
+    fn parse_subsection_header_with_escapes() {
+        let result = read_ini(GIT_STYLE_SUBSECTION_WITH_ESCAPES, None).unwrap();
+        assert_eq!("<branch.feature\"x\\y>(merge=refs/heads/main)", result);
+    }
+
+    const GIT_STYLE_SUBSECTION_WITH_BRACKET: &str = "[branch \"a]b\"]
+merge = refs/heads/main
+";
+
+    #[test]
+    /// A `]` inside the quoted subsection name is ordinary content, not the header's closing
+    /// bracket.
+    fn parse_subsection_header_with_bracket_in_name() {
+        let result = read_ini(GIT_STYLE_SUBSECTION_WITH_BRACKET, None).unwrap();
+        assert_eq!("<branch.a]b>(merge=refs/heads/main)", result);
+    }
+
     const UNEXPECTED_OPTION: &str = "[logging]
 invalid = error
 ";
@@ -380,4 +845,381 @@ invalid = error
             Err(IniError::Handler(TestError::InvalidOption))
         ));
     }
+
+    const QUOTED_INI: &str = "[logging]
+path = \"  /var/log/app.log \"
+message = \"line one\\nline two\"
+escaped = \"a\\;b\\#c\\\"d\\\\e\"
+hex = \"\\x41\\x42\"
+";
+
+    #[test]
+    fn parse_quoted_value() -> ParserResult<()> {
+        let result = read_ini_with_escapes(QUOTED_INI)?;
+        assert_eq!(
+            "<logging>(path=  /var/log/app.log )(message=line one\nline two)(escaped=a;b#c\"d\\e)(hex=AB)",
+            result
+        );
+        Ok(())
+    }
+
+    const QUOTED_INI_WITH_TRAILING_COMMENT: &str = "[logging]
+path = \"/var/log/app.log\" ; absolute path
+";
+
+    #[test]
+    /// Stripping a comment after a quoted value requires inline comments to be enabled too,
+    /// just like the unquoted path.
+    fn parse_quoted_value_with_trailing_comment() -> ParserResult<()> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(QUOTED_INI_WITH_TRAILING_COMMENT).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler)
+            .with_escape_policy(true)
+            .with_inline_comments(true);
+        parser.parse(buf)?;
+        assert_eq!(
+            "<logging>/*absolute path*/(path=/var/log/app.log)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    /// Without inline comments enabled, content after a quoted value isn't treated as a
+    /// comment even if it looks like one.
+    fn parse_quoted_value_with_trailing_comment_marker_but_no_inline_comments() {
+        let res = dbg!(read_ini_with_escapes(QUOTED_INI_WITH_TRAILING_COMMENT));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::TrailingCharacters,
+                ..
+            }))
+        ));
+    }
+
+    const UNTERMINATED_QUOTE: &str = "[logging]
+path = \"/var/log/app.log
+";
+
+    #[test]
+    fn parse_unterminated_quote() {
+        let res = dbg!(read_ini_with_escapes(UNTERMINATED_QUOTE));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::UnterminatedQuote,
+                ..
+            }))
+        ));
+    }
+
+    const QUOTED_VALUE_WITH_INVALID_ESCAPE: &str = "[logging]
+path = \"\\xZZ\"
+";
+
+    #[test]
+    fn parse_invalid_escape() {
+        let res = dbg!(read_ini_with_escapes(QUOTED_VALUE_WITH_INVALID_ESCAPE));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::InvalidEscape,
+                ..
+            }))
+        ));
+    }
+
+    const QUOTED_VALUE_WITH_NON_ASCII_HEX_ESCAPE: &str = "[logging]
+path = \"\\xE9\"
+";
+
+    #[test]
+    /// `\xHH` is restricted to the ASCII range: a lone high-bit byte isn't a character by
+    /// itself and this escape doesn't model UTF-8 continuation bytes.
+    fn parse_non_ascii_hex_escape_is_rejected() {
+        let res = dbg!(read_ini_with_escapes(
+            QUOTED_VALUE_WITH_NON_ASCII_HEX_ESCAPE
+        ));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::InvalidEscape,
+                ..
+            }))
+        ));
+    }
+
+    const QUOTED_VALUE_WITH_TRAILING_GARBAGE: &str = "[logging]
+path = \"/var/log/app.log\" extra
+";
+
+    #[test]
+    fn parse_quoted_value_with_trailing_garbage() {
+        let res = dbg!(read_ini_with_escapes(QUOTED_VALUE_WITH_TRAILING_GARBAGE));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                lineno: 2,
+                kind: ErrorKind::TrailingCharacters,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    /// Without the escape policy, a quoted value is left untouched
+    fn parse_quoted_value_without_escape_policy() -> ParserResult<()> {
+        let result = read_ini(QUOTED_INI_WITH_TRAILING_COMMENT, None)?;
+        assert_eq!(
+            "<logging>(path=\"/var/log/app.log\" ; absolute path)",
+            result
+        );
+        Ok(())
+    }
+
+    fn read_ini_with_continuation(content: &str) -> ParserResult<String> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(content).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_line_continuation(true);
+        parser.parse(buf)?;
+        handler
+            .get()
+            .map(|s| s.to_string())
+            .map_err(ParserError::Handler)
+    }
+
+    const CONTINUED_INI: &str = "[paths]
+list = one,\\
+two,\\
+three
+";
+
+    #[test]
+    fn parse_line_continuation() -> ParserResult<()> {
+        let result = read_ini_with_continuation(CONTINUED_INI)?;
+        assert_eq!("<paths>(list=one,two,three)", result);
+        Ok(())
+    }
+
+    const CONTINUED_INI_THEN_INVALID_LINE: &str = "[paths]
+list = one,\\
+two,
+three error";
+
+    #[test]
+    /// Lines following a continuation resume being parsed on their own line number
+    fn parse_line_continuation_resumes_own_lineno() {
+        let res = dbg!(read_ini_with_continuation(CONTINUED_INI_THEN_INVALID_LINE));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError { lineno: 4, .. }))
+        ));
+    }
+
+    const TRAILING_BACKSLASH_AT_EOF: &str = "[paths]
+list = one\\";
+
+    #[test]
+    /// A trailing backslash with no following line is kept as a literal backslash
+    fn parse_trailing_backslash_at_eof() -> ParserResult<()> {
+        let result = read_ini_with_continuation(TRAILING_BACKSLASH_AT_EOF)?;
+        assert_eq!("<paths>(list=one\\)", result);
+        Ok(())
+    }
+
+    #[test]
+    /// Without line continuation enabled, the continued lines are parsed on their own and fail
+    fn parse_line_continuation_disabled_by_default() {
+        let res = dbg!(read_ini(CONTINUED_INI, None));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError { lineno: 3, .. }))
+        ));
+    }
+
+    const EMPTY_KEY: &str = "[logging]
+ = error
+";
+
+    #[test]
+    fn parse_empty_key() {
+        let res = dbg!(read_ini(EMPTY_KEY, None));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError {
+                kind: ErrorKind::EmptyKey,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn error_reports_column() {
+        let res = dbg!(read_ini(INVALID_OPTION, None));
+        assert!(matches!(
+            res,
+            Err(IniError::InvalidLine(LineError { column: 1, .. }))
+        ));
+    }
+
+    struct LenientHandler {
+        stream: io::Cursor<Vec<u8>>,
+        skip_bad_lines: bool,
+    }
+
+    impl LenientHandler {
+        fn new(skip_bad_lines: bool) -> Self {
+            Self {
+                stream: io::Cursor::new(Vec::<u8>::new()),
+                skip_bad_lines,
+            }
+        }
+
+        fn get(&self) -> Result<&str, TestError> {
+            str::from_utf8(self.stream.get_ref()).map_err(TestError::Utf8)
+        }
+    }
+
+    impl IniHandler for LenientHandler {
+        type Error = TestError;
+
+        fn section(&mut self, name: &str) -> Result<(), Self::Error> {
+            write!(self.stream, "<{}>", name).map_err(Self::Error::Io)
+        }
+
+        fn option(&mut self, name: &str, value: &str) -> Result<(), Self::Error> {
+            write!(self.stream, "({}={})", name, value).map_err(Self::Error::Io)
+        }
+
+        fn error(&mut self, _err: &IniError<Self::Error>) -> Result<(), Self::Error> {
+            if self.skip_bad_lines {
+                Ok(())
+            } else {
+                Err(TestError::InvalidOption)
+            }
+        }
+    }
+
+    const LENIENT_INI: &str = "[logging]
+level error
+format = plain
+not an option either
+retries = 3
+";
+
+    #[test]
+    fn parse_lenient_skips_bad_lines_and_reports_them() -> ParserResult<()> {
+        let mut handler = LenientHandler::new(true);
+        let buf = new_input_stream(LENIENT_INI).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler);
+        let errors = parser.parse_lenient(buf)?;
+        assert_eq!(2, errors.len());
+        assert_eq!(
+            "<logging>(format=plain)(retries=3)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_lenient_aborts_when_handler_declines_to_skip() {
+        let mut handler = LenientHandler::new(false);
+        let buf = new_input_stream(LENIENT_INI).unwrap();
+        let mut parser = IniParser::new(&mut handler);
+        let res = dbg!(parser.parse_lenient(buf));
+        assert!(matches!(
+            res,
+            Err(IniError::Handler(TestError::InvalidOption))
+        ));
+    }
+
+    const PROPERTIES_STYLE_INI: &str = "[logging]
+level: error
+retries = 3
+";
+
+    #[test]
+    fn parse_with_extra_delimiters() -> ParserResult<()> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(PROPERTIES_STYLE_INI).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_delimiters(&['=', ':']);
+        parser.parse(buf)?;
+        assert_eq!(
+            "<logging>(level=error)(retries=3)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    const HASH_COMMENT_INI: &str = "; semicolon comment
+# hash comment
+[logging]
+level = error
+";
+
+    #[test]
+    fn parse_with_extra_comment_starts() -> ParserResult<()> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(HASH_COMMENT_INI).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_comment_starts(&[";", "#"]);
+        parser.parse(buf)?;
+        assert_eq!(
+            "/*semicolon comment*//*hash comment*/<logging>(level=error)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    const INLINE_COMMENT_INI: &str = "[logging]
+level = error ; the log level
+path = /var/log/app.log
+";
+
+    #[test]
+    fn parse_with_inline_comments() -> ParserResult<()> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(INLINE_COMMENT_INI).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_inline_comments(true);
+        parser.parse(buf)?;
+        assert_eq!(
+            "<logging>(level=error)/*the log level*/(path=/var/log/app.log)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    const INLINE_COMMENT_MARKER_MID_WORD_INI: &str = "[logging]
+password = sec;ret
+";
+
+    #[test]
+    /// A comment marker embedded inside a word, with no preceding whitespace, isn't an inline
+    /// comment.
+    fn parse_inline_comments_require_word_boundary() -> ParserResult<()> {
+        let mut handler = Handler::new();
+        let buf = new_input_stream(INLINE_COMMENT_MARKER_MID_WORD_INI).map_err(IniError::Io)?;
+        let mut parser = IniParser::new(&mut handler).with_inline_comments(true);
+        parser.parse(buf)?;
+        assert_eq!(
+            "<logging>(password=sec;ret)",
+            handler.get().map_err(ParserError::Handler)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    /// Without inline comments enabled, the comment marker is kept as part of the value
+    fn parse_without_inline_comments_keeps_marker_in_value() -> ParserResult<()> {
+        let result = read_ini(INLINE_COMMENT_INI, None)?;
+        assert_eq!(
+            "<logging>(level=error ; the log level)(path=/var/log/app.log)",
+            result
+        );
+        Ok(())
+    }
 }
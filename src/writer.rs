@@ -0,0 +1,186 @@
+//! INI format writer, the counterpart of [`IniParser`](crate::IniParser) for producing
+//! well-formed INI output.
+
+use std::io::{self, Write};
+
+/// Encode the characters in `value` that would otherwise be misinterpreted when the value is
+/// re-read as a quoted value (the inverse of the escape decoding done when parsing).
+fn encode_escapes(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => encoded.push_str("\\\\"),
+            '"' => encoded.push_str("\\\""),
+            '\n' => encoded.push_str("\\n"),
+            '\t' => encoded.push_str("\\t"),
+            '\r' => encoded.push_str("\\r"),
+            '\0' => encoded.push_str("\\0"),
+            _ => encoded.push(ch),
+        }
+    }
+    encoded
+}
+
+/// Return true if `value` cannot be written as-is and needs quoting.
+fn needs_quoting(value: &str, start_comment: &str, delimiter: char) -> bool {
+    value != value.trim()
+        || value.contains(['\n', '\t', '\r', '\0', '"', '\\', delimiter])
+        || value.contains(start_comment)
+}
+
+/// Writes well-formed INI output, sharing its comment start character and, optionally, its
+/// quoting/escape policy with [`IniParser`](crate::IniParser) so that values needing escaping
+/// are written back correctly.
+pub struct IniWriter<W: Write> {
+    output: W,
+    start_comment: String,
+    delimiter: char,
+    escape_policy: bool,
+    wrote_section: bool,
+}
+
+impl<W: Write> IniWriter<W> {
+    /// Create a writer using `;` as the start of comment and `=` as the key/value delimiter.
+    pub fn new(output: W) -> Self {
+        Self::with_start_comment(output, ';')
+    }
+
+    /// Create a writer using the given character as start of comment and `=` as the key/value
+    /// delimiter.
+    pub fn with_start_comment(output: W, start_comment: char) -> Self {
+        Self {
+            output,
+            start_comment: format!("{}", start_comment),
+            delimiter: '=',
+            escape_policy: false,
+            wrote_section: false,
+        }
+    }
+
+    /// Use `delimiter` instead of `=` to separate keys from values, matching an
+    /// [`IniParser`](crate::IniParser) configured with
+    /// [`with_delimiters`](crate::IniParser::with_delimiters) for a dialect that doesn't accept
+    /// `=` (e.g. `.properties`-style `:`).
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Enable or disable quoting values that need it (see
+    /// [`IniParser::with_escape_policy`](crate::IniParser::with_escape_policy)).
+    pub fn with_escape_policy(mut self, enable: bool) -> Self {
+        self.escape_policy = enable;
+        self
+    }
+
+    /// Write a `[name]` section header, preceded by a blank line if this is not the first
+    /// section of the document.
+    pub fn write_section(&mut self, name: &str) -> io::Result<()> {
+        if self.wrote_section {
+            writeln!(self.output)?;
+        }
+        self.wrote_section = true;
+        writeln!(self.output, "[{}]", name)
+    }
+
+    /// Write a `key = value` option row (using the configured delimiter in place of `=`),
+    /// quoting and escaping `value` if needed and the escape policy is enabled.
+    pub fn write_option(&mut self, key: &str, value: &str) -> io::Result<()> {
+        if self.escape_policy && needs_quoting(value, &self.start_comment, self.delimiter) {
+            writeln!(
+                self.output,
+                "{} {} \"{}\"",
+                key,
+                self.delimiter,
+                encode_escapes(value)
+            )
+        } else {
+            writeln!(self.output, "{} {} {}", key, self.delimiter, value)
+        }
+    }
+
+    /// Write a comment line.
+    pub fn write_comment(&mut self, text: &str) -> io::Result<()> {
+        writeln!(self.output, "{} {}", self.start_comment, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::IniWriter;
+
+    #[test]
+    fn write_plain_document() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = IniWriter::new(&mut buf);
+            writer.write_option("name", "test suite").unwrap();
+            writer.write_comment("logging section").unwrap();
+            writer.write_section("logging").unwrap();
+            writer.write_option("level", "error").unwrap();
+        }
+        assert_eq!(
+            "name = test suite\n; logging section\n[logging]\nlevel = error\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_blank_line_between_sections() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = IniWriter::new(&mut buf);
+            writer.write_section("a").unwrap();
+            writer.write_option("x", "1").unwrap();
+            writer.write_section("b").unwrap();
+            writer.write_option("y", "2").unwrap();
+        }
+        assert_eq!(
+            "[a]\nx = 1\n\n[b]\ny = 2\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_option_needing_quotes() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = IniWriter::new(&mut buf).with_escape_policy(true);
+            writer.write_option("path", "  /var/log/app.log ").unwrap();
+            writer
+                .write_option("greeting", "line one\nline two")
+                .unwrap();
+        }
+        assert_eq!(
+            "path = \"  /var/log/app.log \"\ngreeting = \"line one\\nline two\"\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_option_with_custom_delimiter() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = IniWriter::new(&mut buf).with_delimiter(':');
+            writer.write_option("url", "http://example.com").unwrap();
+        }
+        assert_eq!(
+            "url : http://example.com\n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_option_without_escape_policy_is_left_untouched() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = IniWriter::new(&mut buf);
+            writer.write_option("path", "  /var/log/app.log ").unwrap();
+        }
+        assert_eq!(
+            "path =   /var/log/app.log \n",
+            String::from_utf8(buf).unwrap()
+        );
+    }
+}